@@ -1,7 +1,7 @@
 use ethers_abi::{Detokenize, Function};
 use ethers_providers::JsonRpcClient;
 use ethers_signers::{Client, Signer};
-use ethers_types::{Address, BlockNumber, TransactionRequest, H256, U256};
+use ethers_types::{AccessList, Address, BlockNumber, TransactionRequest, H256, U256, U64};
 
 use std::{fmt::Debug, marker::PhantomData};
 
@@ -34,6 +34,36 @@ impl<'a, S, P, D: Detokenize> ContractCall<'a, S, P, D> {
         self
     }
 
+    /// Sets the `max_fee_per_gas` field in the transaction to the provided value
+    ///
+    /// Setting this (or [`ContractCall::max_priority_fee_per_gas`]) causes `call`/`send` to
+    /// submit an EIP-1559 transaction. If neither is set, the call falls back to an EIP-2930
+    /// transaction when an access list is set, and to a legacy transaction priced with
+    /// `gas_price` otherwise.
+    pub fn max_fee_per_gas<T: Into<U256>>(mut self, max_fee_per_gas: T) -> Self {
+        self.tx.max_fee_per_gas = Some(max_fee_per_gas.into());
+        self
+    }
+
+    /// Sets the `max_priority_fee_per_gas` field in the transaction to the provided value
+    ///
+    /// See [`ContractCall::max_fee_per_gas`] for how this affects the transaction type.
+    pub fn max_priority_fee_per_gas<T: Into<U256>>(mut self, max_priority_fee_per_gas: T) -> Self {
+        self.tx.max_priority_fee_per_gas = Some(max_priority_fee_per_gas.into());
+        self
+    }
+
+    /// Sets the `access_list` field in the transaction to the provided value
+    ///
+    /// Setting this (and neither [`ContractCall::max_fee_per_gas`] nor
+    /// [`ContractCall::max_priority_fee_per_gas`]) causes `call`/`send` to submit an EIP-2930
+    /// transaction, so the access list isn't silently dropped by a provider that only forwards
+    /// fields recognized by the transaction's declared type.
+    pub fn access_list<T: Into<AccessList>>(mut self, access_list: T) -> Self {
+        self.tx.access_list = Some(access_list.into());
+        self
+    }
+
     /// Sets the `value` field in the transaction to the provided value
     pub fn value<T: Into<U256>>(mut self, value: T) -> Self {
         self.tx.value = Some(value.into());
@@ -68,7 +98,9 @@ where
     /// and return the return type of the transaction without mutating the state
     ///
     /// Note: this function _does not_ send a transaction from your account
-    pub async fn call(self) -> Result<D, ContractError<P>> {
+    pub async fn call(mut self) -> Result<D, ContractError<P>> {
+        self.tx.transaction_type = select_transaction_type(&self.tx);
+
         let bytes = self
             .client
             .call(self.tx, self.block)
@@ -83,7 +115,58 @@ where
     }
 
     /// Signs and broadcasts the provided transaction
-    pub async fn send(self) -> Result<H256, P::Error> {
+    pub async fn send(mut self) -> Result<H256, P::Error> {
+        self.tx.transaction_type = select_transaction_type(&self.tx);
         self.client.send_transaction(self.tx, self.block).await
     }
 }
+
+/// The EIP-2718 transaction type to use for a given transaction:
+/// - `Some(2)` (EIP-1559) if either fee-market field has been set
+/// - `Some(1)` (EIP-2930) if an access list has been set but no fee-market field has
+/// - `None` (legacy, priced with `gas_price`) otherwise
+fn select_transaction_type(tx: &TransactionRequest) -> Option<U64> {
+    if tx.max_fee_per_gas.is_some() || tx.max_priority_fee_per_gas.is_some() {
+        Some(U64::from(2))
+    } else if tx.access_list.is_some() {
+        Some(U64::from(1))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selects_eip1559_transaction_type_when_fee_fields_are_set() {
+        let mut tx = TransactionRequest::default();
+        assert_eq!(select_transaction_type(&tx), None);
+
+        tx.max_fee_per_gas = Some(U256::from(100));
+        assert_eq!(select_transaction_type(&tx), Some(U64::from(2)));
+    }
+
+    #[test]
+    fn selects_eip2930_transaction_type_when_only_access_list_is_set() {
+        let mut tx = TransactionRequest::default();
+        tx.access_list = Some(AccessList::default());
+        assert_eq!(select_transaction_type(&tx), Some(U64::from(1)));
+    }
+
+    #[test]
+    fn fee_fields_take_priority_over_access_list() {
+        let mut tx = TransactionRequest::default();
+        tx.access_list = Some(AccessList::default());
+        tx.max_fee_per_gas = Some(U256::from(100));
+        assert_eq!(select_transaction_type(&tx), Some(U64::from(2)));
+    }
+
+    #[test]
+    fn falls_back_to_legacy_when_only_gas_price_is_set() {
+        let mut tx = TransactionRequest::default();
+        tx.gas_price = Some(U256::from(100));
+        assert_eq!(select_transaction_type(&tx), None);
+    }
+}
@@ -0,0 +1,350 @@
+//! Detects Solidity structs shared verbatim across multiple contracts in a [`crate::MultiAbigen`]
+//! and hoists them into a single `shared_types` module so that values can be passed between
+//! generated bindings instead of each contract emitting its own, incompatible copy.
+
+use crate::Abigen;
+use anyhow::Result;
+use inflector::Inflector;
+use std::collections::{BTreeMap, HashMap};
+
+/// A struct type that is used, with an identical shape, by more than one contract.
+struct SharedStruct {
+    name: String,
+    fields: Vec<(String, String)>,
+}
+
+/// A struct's (Rust name, `(field name, field Rust type)` pairs) shape, as extracted from the ABI.
+type Structure = (String, Vec<(String, String)>);
+
+/// Scans every `abigen`'s ABI for struct-shaped tuple components and returns the generated
+/// source of a `shared_types` module plus the set of struct names it now owns, so callers can
+/// strip the duplicate local definitions out of each contract's own file.
+///
+/// Only contracts whose `abi_source` resolves to raw ABI JSON are considered; inline
+/// human-readable ABIs are best-effort skipped since struct bodies aren't available post-parse.
+pub fn extract_shared_types(abigens: &[Abigen]) -> Result<Option<(String, Vec<String>)>> {
+    // canonical tuple signature -> chosen struct, and -> number of contracts that reference it
+    let mut seen: HashMap<String, SharedStruct> = HashMap::new();
+    let mut contract_count: HashMap<String, usize> = HashMap::new();
+
+    for abigen in abigens {
+        let Ok(raw) = abigen.abi_source().get() else { continue };
+        let Ok(abi) = serde_json::from_str::<serde_json::Value>(&raw) else { continue };
+        let Some(items) = abi.as_array() else { continue };
+
+        let mut structs_in_contract = BTreeMap::new();
+        for item in items {
+            for components in struct_components(item) {
+                if let Some((signature, structure)) = as_struct(components) {
+                    structs_in_contract.insert(signature, structure);
+                }
+            }
+        }
+
+        for (signature, (name, fields)) in structs_in_contract {
+            *contract_count.entry(signature.clone()).or_insert(0) += 1;
+            seen.entry(signature).or_insert(SharedStruct { name, fields });
+        }
+    }
+
+    let mut shared: Vec<_> =
+        seen.into_iter().filter(|(sig, _)| contract_count.get(sig).copied().unwrap_or(0) > 1).collect();
+    if shared.is_empty() {
+        return Ok(None)
+    }
+    shared.sort_by(|(_, a), (_, b)| a.name.cmp(&b.name));
+
+    let mut module = String::from("//! Struct types shared, verbatim, across more than one contract in this module.\n\n");
+    module.push_str("use ethers_contract::{EthAbiCodec, EthAbiType};\n\n");
+    let mut names = Vec::new();
+    for (_, structure) in &shared {
+        names.push(structure.name.clone());
+        // `EthAbiType`/`EthAbiCodec` are the same derives the per-contract copy of this struct
+        // would have carried before being hoisted here - without them the struct has no
+        // `Tokenize`/`Detokenize` impl and can't actually be passed into or returned from a
+        // generated contract method.
+        module.push_str("#[derive(Clone, Debug, Eq, PartialEq, EthAbiType, EthAbiCodec)]\n");
+        module.push_str(&format!("pub struct {} {{\n", structure.name));
+        for (field_name, field_type) in &structure.fields {
+            module.push_str(&format!("    pub {}: {},\n", field_name.to_snake_case(), field_type));
+        }
+        module.push_str("}\n\n");
+    }
+
+    Ok(Some((module, names)))
+}
+
+/// Recursively walks an ABI item (function/event/error entry) yielding every input/output param,
+/// at any nesting depth, whose `internalType` names a `struct` - i.e. every struct reference.
+fn struct_components(item: &serde_json::Value) -> Vec<&serde_json::Value> {
+    let mut out = Vec::new();
+    for key in ["inputs", "outputs"] {
+        if let Some(params) = item.get(key).and_then(|v| v.as_array()) {
+            for param in params {
+                walk_param(param, &mut out);
+            }
+        }
+    }
+    out
+}
+
+fn walk_param<'a>(param: &'a serde_json::Value, out: &mut Vec<&'a serde_json::Value>) {
+    let is_struct = param
+        .get("internalType")
+        .and_then(|v| v.as_str())
+        .map(|s| s.trim_start_matches("struct ").len() != s.len())
+        .unwrap_or(false);
+
+    if is_struct {
+        out.push(param);
+    }
+    if let Some(components) = param.get("components").and_then(|v| v.as_array()) {
+        for component in components {
+            walk_param(component, out);
+        }
+    }
+}
+
+/// Converts a `tuple` ABI param with `components` into `(canonical signature, (name, fields))`.
+///
+/// The signature includes both the field *names* and their types, in declaration order, so two
+/// structs that merely share a name and field types - but disagree on what those fields are
+/// called - are never merged into one shared type.
+fn as_struct(param: &serde_json::Value) -> Option<(String, Structure)> {
+    let internal_type = param.get("internalType")?.as_str()?;
+    let name = struct_name(internal_type);
+    let components = param.get("components")?.as_array()?;
+
+    let mut fields = Vec::new();
+    let mut signature_parts = Vec::new();
+    for component in components {
+        let field_name = component.get("name")?.as_str()?.to_owned();
+        let solidity_type = component.get("type")?.as_str()?.to_owned();
+        signature_parts.push(format!("{}:{}", field_name, solidity_type));
+        fields.push((field_name, component_rust_type(component)?));
+    }
+
+    let signature = format!("{}({})", name, signature_parts.join(","));
+    Some((signature, (name, fields)))
+}
+
+/// Derives a struct's Rust (Pascal-case) name from its ABI `internalType`, e.g.
+/// `"struct Test.Order[]"` -> `Order`. Shared between a struct's own definition and any sibling
+/// field that references it by type, so the two always agree on a name.
+fn struct_name(internal_type: &str) -> String {
+    let internal_type = internal_type.trim_start_matches("struct ");
+    internal_type.rsplit('.').next().unwrap_or(internal_type).trim_end_matches("[]").to_pascal_case()
+}
+
+/// Resolves a single ABI component to the Rust type its generated field should have: the real
+/// struct name for a nested `tuple`, the same width-aware primitive mapping [`solidity_to_rust_type`]
+/// uses, or a `Vec<_>` of either for an array of them.
+fn component_rust_type(component: &serde_json::Value) -> Option<String> {
+    let ty = component.get("type")?.as_str()?;
+
+    if let Some(element_type) = ty.strip_suffix("[]") {
+        let element = component_rust_type_for(component, element_type)?;
+        return Some(format!("::std::vec::Vec<{}>", element))
+    }
+
+    component_rust_type_for(component, ty)
+}
+
+fn component_rust_type_for(component: &serde_json::Value, ty: &str) -> Option<String> {
+    if ty == "tuple" {
+        let internal_type = component.get("internalType")?.as_str()?;
+        return Some(struct_name(internal_type))
+    }
+    Some(solidity_to_rust_type(ty))
+}
+
+/// Removes the local `pub struct {name} { ... }` definition (and its leading attributes, e.g.
+/// derives) for every name in `shared`, returning the rewritten source and the subset of names
+/// that were actually found and removed.
+///
+/// Any remaining trait impls for the struct keep compiling unmodified: the type is still local
+/// to this crate (just defined in `shared_types` instead), which is all Rust's orphan rules
+/// require.
+pub fn strip_shared_structs(source: &str, shared: &[String]) -> (String, Vec<String>) {
+    let mut lines: Vec<String> = source.lines().map(str::to_owned).collect();
+    let mut removed = Vec::new();
+
+    for name in shared {
+        let marker = format!("pub struct {} {{", name);
+        let Some(start) = lines.iter().position(|line| line.trim() == marker) else { continue };
+
+        let mut end = start;
+        let mut depth = 0i32;
+        for (offset, line) in lines[start..].iter().enumerate() {
+            depth += line.matches('{').count() as i32;
+            depth -= line.matches('}').count() as i32;
+            if depth == 0 {
+                end = start + offset;
+                break
+            }
+        }
+
+        let mut attr_start = start;
+        while attr_start > 0 && lines[attr_start - 1].trim_start().starts_with('#') {
+            attr_start -= 1;
+        }
+
+        lines.drain(attr_start..=end);
+        removed.push(name.clone());
+    }
+
+    (lines.join("\n"), removed)
+}
+
+/// A Solidity -> Rust type mapping covering the primitives that show up in shared struct fields,
+/// narrowing integer widths the same way the per-contract codegen does (so a hoisted struct's
+/// field types still match whatever the rest of that contract's file expects); exotic types (e.g.
+/// `mapping`) fall back to `ethers_core::abi::Token`. Array and nested-struct (`tuple`) types are
+/// handled by [`component_rust_type`], which calls back into this for their element type.
+fn solidity_to_rust_type(ty: &str) -> String {
+    match ty {
+        "address" => "ethers_core::types::Address".to_owned(),
+        "bool" => "bool".to_owned(),
+        "string" => "String".to_owned(),
+        "bytes" => "ethers_core::types::Bytes".to_owned(),
+        "uint8" => "u8".to_owned(),
+        "uint16" => "u16".to_owned(),
+        "uint32" => "u32".to_owned(),
+        "uint64" => "u64".to_owned(),
+        "int8" => "i8".to_owned(),
+        "int16" => "i16".to_owned(),
+        "int32" => "i32".to_owned(),
+        "int64" => "i64".to_owned(),
+        t if t.starts_with("uint") || t.starts_with("int") => "ethers_core::types::U256".to_owned(),
+        _ => "ethers_core::abi::Token".to_owned(),
+    }
+}
+
+/// Inserts `addition` (typically a `use` statement) right after any leading inner attributes
+/// (`#![...]`) in `source`, instead of unconditionally at the very top.
+///
+/// Inner attributes must be the first items in a file/module; a `use` statement placed before
+/// them (as a naive prepend would do) is a hard compile error on any generated file that starts
+/// with one (e.g. `#![allow(clippy::all)]`, common in abigen output).
+pub fn insert_after_inner_attributes(source: &str, addition: &str) -> String {
+    let mut lines: Vec<&str> = source.lines().collect();
+    let at = lines.iter().take_while(|line| line.trim_start().starts_with("#!")).count();
+    lines.insert(at, addition);
+    lines.join("\n") + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn abi_with_order(field_a: &str, field_b: &str) -> String {
+        format!(
+            r#"[{{
+                "type": "function",
+                "name": "place",
+                "inputs": [{{
+                    "name": "order",
+                    "type": "tuple",
+                    "internalType": "struct Test.Order",
+                    "components": [
+                        {{"name": "{field_a}", "type": "address", "internalType": "address"}},
+                        {{"name": "{field_b}", "type": "uint256", "internalType": "uint256"}}
+                    ]
+                }}],
+                "outputs": []
+            }}]"#,
+            field_a = field_a,
+            field_b = field_b
+        )
+    }
+
+    #[test]
+    fn hoists_structs_shared_verbatim_across_contracts() {
+        let a = Abigen::new("A", abi_with_order("token", "amount")).unwrap();
+        let b = Abigen::new("B", abi_with_order("token", "amount")).unwrap();
+
+        let (module, names) = extract_shared_types(&[a, b]).unwrap().unwrap();
+        assert_eq!(names, vec!["Order".to_owned()]);
+        assert!(module.contains("use ethers_contract::{EthAbiCodec, EthAbiType};"));
+        assert!(module.contains("EthAbiType, EthAbiCodec"));
+        assert!(module.contains("pub struct Order"));
+        assert!(module.contains("pub token: ethers_core::types::Address"));
+        assert!(module.contains("pub amount: ethers_core::types::U256"));
+    }
+
+    #[test]
+    fn narrows_integer_widths_and_resolves_nested_struct_fields() {
+        let abi = r#"[{
+            "type": "function",
+            "name": "place",
+            "inputs": [{
+                "name": "order",
+                "type": "tuple",
+                "internalType": "struct Test.Order",
+                "components": [
+                    {"name": "id", "type": "uint8", "internalType": "uint8"},
+                    {"name": "nonce", "type": "uint64", "internalType": "uint64"},
+                    {
+                        "name": "fill",
+                        "type": "tuple",
+                        "internalType": "struct Test.Fill",
+                        "components": [
+                            {"name": "amount", "type": "uint256", "internalType": "uint256"}
+                        ]
+                    }
+                ]
+            }],
+            "outputs": []
+        }]"#;
+
+        let a = Abigen::new("A", abi.to_owned()).unwrap();
+        let b = Abigen::new("B", abi.to_owned()).unwrap();
+
+        let (module, names) = extract_shared_types(&[a, b]).unwrap().unwrap();
+        assert_eq!(names, vec!["Fill".to_owned(), "Order".to_owned()]);
+        assert!(module.contains("pub id: u8,"));
+        assert!(module.contains("pub nonce: u64,"));
+        assert!(module.contains("pub fill: Fill,"));
+    }
+
+    #[test]
+    fn does_not_merge_same_named_structs_with_different_field_names() {
+        // Same name, same field *types*, but different field *names* - these are not the same
+        // type and must not be collapsed into one shared struct.
+        let a = Abigen::new("A", abi_with_order("token", "amount")).unwrap();
+        let b = Abigen::new("B", abi_with_order("asset", "qty")).unwrap();
+
+        assert!(extract_shared_types(&[a, b]).unwrap().is_none());
+    }
+
+    #[test]
+    fn strips_only_the_named_struct_definitions() {
+        let source = "#[derive(Clone, Debug)]\npub struct Order {\n    pub token: Address,\n}\n\npub struct Other {\n    pub x: u8,\n}\n";
+        let (rewritten, removed) = strip_shared_structs(source, &["Order".to_owned()]);
+
+        assert_eq!(removed, vec!["Order".to_owned()]);
+        assert!(!rewritten.contains("pub struct Order"));
+        assert!(rewritten.contains("pub struct Other"));
+    }
+
+    #[test]
+    fn inserts_after_leading_inner_attributes() {
+        let source = "#![allow(clippy::all)]\n#![allow(dead_code)]\npub struct Contract;\n";
+
+        let result = insert_after_inner_attributes(source, "use super::shared_types::Order;");
+
+        let lines: Vec<&str> = result.lines().collect();
+        assert_eq!(lines[0], "#![allow(clippy::all)]");
+        assert_eq!(lines[1], "#![allow(dead_code)]");
+        assert_eq!(lines[2], "use super::shared_types::Order;");
+    }
+
+    #[test]
+    fn inserts_at_the_top_when_there_are_no_inner_attributes() {
+        let source = "pub struct Contract;\n";
+
+        let result = insert_after_inner_attributes(source, "use super::shared_types::Order;");
+
+        assert_eq!(result.lines().next(), Some("use super::shared_types::Order;"));
+    }
+}
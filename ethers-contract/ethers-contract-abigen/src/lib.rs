@@ -14,11 +14,16 @@ mod test_macros;
 pub mod contract;
 use contract::Context;
 
+mod dedup;
+mod natspec;
 pub mod rawabi;
 mod rustfmt;
+mod solc;
 mod source;
 mod util;
 
+use natspec::NatSpec;
+
 pub use ethers_core::types::Address;
 pub use source::Source;
 pub use util::parse_address;
@@ -70,10 +75,29 @@ pub struct Abigen {
 
     /// Manually specified event name aliases.
     event_aliases: HashMap<String, String>,
+
+    /// The contract's deploy (creation) bytecode, hex encoded, used to generate a `deploy`
+    /// helper on the bindings. Populated automatically by [`MultiAbigen::from_solidity_files`];
+    /// `None` otherwise, including for a [`Source::Solidity`] passed to [`Abigen::new`].
+    bytecode: Option<String>,
+
+    /// Whether to attach NatSpec documentation (`@notice`/`@dev`/...) to generated methods
+    /// (including their parameter descriptions) and event structs as `///` rustdoc, when it's
+    /// available.
+    emit_natspec: bool,
+
+    /// Pre-resolved NatSpec documentation, e.g. from a compiler's `devdoc`/`userdoc` output.
+    /// When unset and `emit_natspec` is on, a [`Source::Solidity`] source falls back to parsing
+    /// its own inline comments.
+    natspec: Option<NatSpec>,
 }
 
 impl Abigen {
     /// Creates a new builder with the given ABI JSON source.
+    ///
+    /// `abi_source` accepts a path to a local JSON file, an inline (human-readable or raw JSON)
+    /// ABI, or an `etherscan:<address>` / bare checksummed address string to resolve the
+    /// verified ABI from a block explorer (see [`Source`]).
     pub fn new<S: AsRef<str>>(contract_name: &str, abi_source: S) -> Result<Self> {
         let abi_source = abi_source.as_ref().parse()?;
         Ok(Self {
@@ -83,9 +107,63 @@ impl Abigen {
             event_derives: Vec::new(),
             event_aliases: HashMap::new(),
             rustfmt: true,
+            bytecode: None,
+            emit_natspec: false,
+            natspec: None,
         })
     }
 
+    /// The source this builder will read its ABI from.
+    pub(crate) fn abi_source(&self) -> &Source {
+        &self.abi_source
+    }
+
+    /// Attaches deploy (creation) bytecode to the generated bindings, enabling a `deploy` helper
+    /// on the bound contract. Set automatically for contracts created by
+    /// [`MultiAbigen::from_solidity_files`].
+    #[must_use]
+    pub fn with_bytecode<S: Into<String>>(mut self, bytecode: S) -> Self {
+        self.bytecode = Some(bytecode.into());
+        self
+    }
+
+    /// Attaches pre-resolved NatSpec documentation, e.g. parsed from a compiler's
+    /// `devdoc`/`userdoc` output. Automatically set by [`MultiAbigen::from_solidity_files`].
+    #[must_use]
+    pub(crate) fn with_natspec(mut self, natspec: NatSpec) -> Self {
+        self.natspec = Some(natspec);
+        self
+    }
+
+    /// Whether to attach NatSpec documentation (`@notice`/`@dev`/`@param`) to generated methods
+    /// and event structs as `///` rustdoc, when the ABI is accompanied by compiler `devdoc`/
+    /// `userdoc` metadata or the source is a [`Source::Solidity`] file with inline comments.
+    ///
+    /// Defaults to `false`. This is most useful for the `src/contracts` workflow recommended by
+    /// [`MultiAbigen`], where the generated code is checked into the repository and read (and
+    /// hovered over in an IDE) directly.
+    #[must_use]
+    pub fn emit_natspec(mut self, emit_natspec: bool) -> Self {
+        self.emit_natspec = emit_natspec;
+        self
+    }
+
+    /// Resolves the NatSpec documentation to attach, if `emit_natspec` is set.
+    fn resolve_natspec(&self) -> Option<NatSpec> {
+        if !self.emit_natspec {
+            return None
+        }
+        if let Some(natspec) = &self.natspec {
+            return Some(natspec.clone())
+        }
+        if let Source::Solidity(path) = &self.abi_source {
+            if let Ok(source) = fs::read_to_string(path) {
+                return Some(natspec::from_solidity_source(&source))
+            }
+        }
+        None
+    }
+
     /// Manually adds a solidity event alias to specify what the event struct
     /// and function name will be in Rust.
     #[must_use]
@@ -138,8 +216,9 @@ impl Abigen {
     /// Generates the contract bindings.
     pub fn generate(self) -> Result<ContractBindings> {
         let rustfmt = self.rustfmt;
+        let natspec = self.resolve_natspec();
         let tokens = Context::from_abigen(self)?.expand()?.into_tokens();
-        Ok(ContractBindings { tokens, rustfmt })
+        Ok(ContractBindings { tokens, rustfmt, natspec })
     }
 }
 
@@ -150,6 +229,8 @@ pub struct ContractBindings {
     tokens: TokenStream,
     /// The output options used for serialization.
     rustfmt: bool,
+    /// NatSpec documentation to attach to generated methods and event structs, if any.
+    natspec: Option<NatSpec>,
 }
 
 impl ContractBindings {
@@ -161,11 +242,17 @@ impl ContractBindings {
         let source = {
             let raw = self.tokens.to_string();
 
-            if self.rustfmt {
-                rustfmt::format(&raw).unwrap_or(raw)
-            } else {
-                raw
+            // `TokenStream::to_string()` doesn't preserve newlines, so `natspec::inject`'s
+            // line-based matching needs to run against already-formatted, multi-line source -
+            // never against the raw token text.
+            let mut formatted =
+                if self.rustfmt { rustfmt::format(&raw).unwrap_or(raw) } else { raw };
+
+            if let Some(natspec) = &self.natspec {
+                formatted = natspec::inject(&formatted, natspec);
             }
+
+            formatted
         };
 
         w.write_all(source.as_bytes())?;
@@ -217,6 +304,10 @@ pub struct MultiAbigen {
     /// whether to write all contracts in a single file instead of separated modules
     single_file: bool,
 
+    /// whether to hoist struct types shared verbatim across multiple contracts into a common
+    /// `shared_types` module instead of emitting an incompatible copy per contract
+    shared_types: bool,
+
     abigens: Vec<Abigen>,
 }
 
@@ -225,10 +316,20 @@ impl MultiAbigen {
     pub fn from_abigen(abis: impl IntoIterator<Item = Abigen>) -> Self {
         Self {
             single_file: false,
+            shared_types: true,
             abigens: abis.into_iter().map(|abi| abi.rustfmt(true)).collect(),
         }
     }
 
+    /// Sets whether struct types shared verbatim across multiple contracts should be hoisted
+    /// into a common `shared_types` module. Defaults to `true`; only takes effect in
+    /// multi-module mode (i.e. when [`MultiAbigen::single_file`] is not set).
+    #[must_use]
+    pub fn shared_types(mut self, shared_types: bool) -> Self {
+        self.shared_types = shared_types;
+        self
+    }
+
     /// Create a new instance from a series (`contract name`, `abi_source`)
     ///
     /// See `Abigen::new`
@@ -275,6 +376,44 @@ impl MultiAbigen {
         Self::new(abis)
     }
 
+    /// Compiles every `.sol` file found (recursively) under `dir` with a locally installed
+    /// `solc` and creates one `Abigen` per contract the compiler emits, with its deploy
+    /// bytecode attached.
+    ///
+    /// This is the natural complement to [`MultiAbigen::from_json_files`]: it lets a build
+    /// script go straight from Solidity sources to a generated `src/contracts` module without
+    /// a separate compilation step.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use ethers_contract_abigen::MultiAbigen;
+    /// let gen = MultiAbigen::from_solidity_files("./contracts").unwrap();
+    /// gen.write_to_module("./src/contracts").unwrap();
+    /// ```
+    pub fn from_solidity_files(dir: impl AsRef<Path>) -> Result<Self> {
+        let contracts = solc::compile_dir(dir)?;
+        let abigens = contracts
+            .into_iter()
+            .map(|contract| {
+                let mut abigen = Abigen::new(&contract.name, contract.abi)?;
+                if let Some(bytecode) = contract.bytecode {
+                    abigen = abigen.with_bytecode(bytecode);
+                }
+                if contract.devdoc.is_some() || contract.userdoc.is_some() {
+                    let natspec = natspec::from_compiler_output(
+                        contract.devdoc.as_deref(),
+                        contract.userdoc.as_deref(),
+                    );
+                    abigen = abigen.with_natspec(natspec);
+                }
+                Ok(abigen)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self::from_abigen(abigens))
+    }
+
     /// Write all bindings into a single rust file instead of separate modules
     #[must_use]
     pub fn single_file(mut self) -> Self {
@@ -315,6 +454,12 @@ impl MultiAbigen {
         let module = module.as_ref();
         fs::create_dir_all(module)?;
 
+        let shared = if !self.single_file && self.shared_types {
+            dedup::extract_shared_types(&self.abigens)?
+        } else {
+            None
+        };
+
         let mut contracts_mod =
             b"/// This module contains all the autogenerated abigen! contract bindings\n".to_vec();
 
@@ -328,11 +473,27 @@ impl MultiAbigen {
             } else {
                 // create a contract rust file
                 let output = module.join(format!("{}.rs", name));
-                bindings.write_to_file(output)?;
+                bindings.write_to_file(&output)?;
+
+                if let Some((_, shared_names)) = &shared {
+                    let source = fs::read_to_string(&output)?;
+                    let (source, removed) = dedup::strip_shared_structs(&source, shared_names);
+                    if !removed.is_empty() {
+                        let uses = format!("use super::shared_types::{{{}}};", removed.join(", "));
+                        let source = dedup::insert_after_inner_attributes(&source, &uses);
+                        fs::write(&output, source)?;
+                    }
+                }
+
                 modules.push(format!("pub mod {};", name));
             }
         }
 
+        if let Some((shared_source, _)) = &shared {
+            fs::write(module.join("shared_types.rs"), shared_source)?;
+            modules.push("pub mod shared_types;".to_owned());
+        }
+
         if !modules.is_empty() {
             modules.sort();
             write!(contracts_mod, "{}", modules.join("\n"))?;
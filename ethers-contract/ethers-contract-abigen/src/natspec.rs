@@ -0,0 +1,317 @@
+//! Extracts NatSpec (`@notice`/`@dev`/`@param`/`@return`) documentation for contract methods and
+//! events so it can be attached as `///` rustdoc on the generated bindings.
+
+use inflector::Inflector;
+use std::collections::{HashMap, HashSet};
+
+/// Per-member doc comment, already formatted as `///`-prefixed lines ready to be inserted above
+/// the matching generated item.
+///
+/// Keyed by the member's full Solidity signature (e.g. `"transfer(address,uint256)"`), not just
+/// its bare name, so overloaded functions each keep their own doc instead of colliding into one.
+pub type NatSpec = HashMap<String, String>;
+
+/// Builds a [`NatSpec`] map from a compiler's `devdoc`/`userdoc` JSON output, as produced by
+/// `solc --combined-json devdoc,userdoc`.
+pub fn from_compiler_output(devdoc: Option<&str>, userdoc: Option<&str>) -> NatSpec {
+    let mut docs: HashMap<String, Vec<String>> = HashMap::new();
+
+    for raw in [userdoc, devdoc].into_iter().flatten() {
+        let Ok(json) = serde_json::from_str::<serde_json::Value>(raw) else { continue };
+        for section in ["methods", "events"] {
+            let Some(members) = json.get(section).and_then(|v| v.as_object()) else { continue };
+            for (signature, doc) in members {
+                if let Some(notice) = doc.get("notice").and_then(|v| v.as_str()) {
+                    docs.entry(signature.clone()).or_default().push(notice.to_owned());
+                }
+                if let Some(details) = doc.get("details").and_then(|v| v.as_str()) {
+                    docs.entry(signature.clone()).or_default().push(details.to_owned());
+                }
+                for key in ["params", "returns"] {
+                    let Some(entries) = doc.get(key).and_then(|v| v.as_object()) else { continue };
+                    for (name, text) in entries {
+                        if let Some(text) = text.as_str() {
+                            docs.entry(signature.clone()).or_default().push(format!("`{}`: {}", name, text));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    docs.into_iter().map(|(signature, lines)| (signature, format_doc(&lines))).collect()
+}
+
+/// Best-effort extraction of NatSpec directly from a `.sol` file's own source text, for sources
+/// that weren't compiled through `solc` (so no `devdoc`/`userdoc` is available).
+///
+/// Members are keyed by their name and parameter list exactly as written on the declaration line,
+/// parameter names included (e.g. `"transfer(address to, uint256 amount)"`).
+pub fn from_solidity_source(source: &str) -> NatSpec {
+    let mut docs = HashMap::new();
+    let mut pending = Vec::new();
+
+    for line in source.lines() {
+        let trimmed = line.trim();
+        if let Some(comment) = trimmed.strip_prefix("///") {
+            pending.push(comment.trim().to_owned());
+        } else if trimmed.starts_with("/**") {
+            // start of a block comment; body lines are handled by the `*` branch below
+        } else if trimmed.starts_with("*/") {
+            // end of a block comment; the declaration line follows
+        } else if let Some(comment) = trimmed.strip_prefix('*') {
+            pending.push(comment.trim().to_owned());
+        } else if let Some(signature) = declaration_signature(trimmed) {
+            if !pending.is_empty() {
+                docs.insert(signature, format_doc(&pending));
+            }
+            pending.clear();
+        } else if !trimmed.is_empty() {
+            pending.clear();
+        }
+    }
+
+    docs
+}
+
+/// Inserts the doc comments from `docs` directly above the matching `pub fn` / event filter
+/// struct in already-formatted binding source.
+///
+/// Functions are matched by both name *and* parameter arity. Two marker shapes are tried for a
+/// given signature, since which one the real codegen emits for an overloaded method couldn't be
+/// confirmed against `contract::Context` (not present in this tree):
+/// - `pub fn {name}(` - the bare snake-cased name, checked against the candidate line's own
+///   arity, for a codegen that disambiguates overloads by parameter count alone
+/// - `pub fn {name}_with_{arity}_args(` - the alias ethers-rs's actual codegen uses for a
+///   conflicting overload, which already encodes the arity in the name
+///
+/// Either way, a `claimed` set of line indices ensures each generated function receives at most
+/// one doc, and that two overloads never end up pointing at the same line.
+pub fn inject(source: &str, docs: &NatSpec) -> String {
+    let mut lines: Vec<String> = source.lines().map(str::to_owned).collect();
+
+    let mut insertions: Vec<(usize, String)> = Vec::new();
+    let mut claimed: HashSet<usize> = HashSet::new();
+    for (signature, doc) in docs {
+        let (name, params) = split_signature(signature);
+        let snake = name.to_snake_case();
+        let pascal = name.to_pascal_case();
+        let arity = param_count(params);
+        let fn_marker = format!("pub fn {}(", snake);
+        let aliased_fn_marker = format!("pub fn {}_with_{}_args(", snake, arity);
+        let event_marker = format!("pub struct {}Filter", pascal);
+
+        let candidate = lines.iter().enumerate().position(|(i, line)| {
+            if claimed.contains(&i) {
+                return false
+            }
+            let trimmed = line.trim_start();
+            if trimmed.starts_with(&event_marker) || trimmed.starts_with(&aliased_fn_marker) {
+                return true
+            }
+            trimmed.starts_with(&fn_marker) && function_arity(&lines, i) == arity
+        });
+
+        if let Some(i) = candidate {
+            claimed.insert(i);
+            insertions.push((i, doc.clone()));
+        }
+    }
+
+    // Insert from the bottom up so earlier indices stay valid as we go.
+    insertions.sort_by_key(|(i, _)| std::cmp::Reverse(*i));
+    for (i, doc) in insertions {
+        let indent: String = lines[i].chars().take_while(|c| c.is_whitespace()).collect();
+        for doc_line in doc.lines().rev() {
+            lines.insert(i, format!("{}{}", indent, doc_line));
+        }
+    }
+
+    lines.join("\n")
+}
+
+/// Splits a Solidity signature (`"name(type1,type2)"`) into its name and raw parameter list text.
+fn split_signature(signature: &str) -> (&str, &str) {
+    let name = signature.split('(').next().unwrap_or(signature);
+    let params = signature.find('(').and_then(|start| signature.rfind(')').map(|end| &signature[start + 1..end])).unwrap_or("");
+    (name, params)
+}
+
+/// Counts the top-level, comma-separated parameters in `params`, tracking bracket/paren/angle
+/// depth so generic or tuple-typed parameters (`(uint256,address)[]`, `mapping(...)`) aren't
+/// miscounted as multiple parameters.
+fn param_count(params: &str) -> usize {
+    if params.trim().is_empty() {
+        return 0
+    }
+
+    let mut depth = 0i32;
+    let mut count = 1usize;
+    for c in params.chars() {
+        match c {
+            '(' | '[' | '<' => depth += 1,
+            ')' | ']' | '>' => depth -= 1,
+            ',' if depth == 0 => count += 1,
+            _ => {}
+        }
+    }
+    count
+}
+
+/// Scans the generated function declaration starting at `lines[start]` and returns its Solidity
+/// parameter arity (i.e. excluding the leading `&self`/`&mut self` receiver that every generated
+/// binding method takes), concatenating subsequent lines until the opening paren's matching close
+/// is found - a rustfmt'd signature with several parameters is typically wrapped across multiple
+/// lines.
+fn function_arity(lines: &[String], start: usize) -> usize {
+    let mut signature = String::new();
+    let mut depth = 0i32;
+    let mut seen_open = false;
+
+    for line in &lines[start..] {
+        for c in line.chars() {
+            match c {
+                '(' => {
+                    depth += 1;
+                    seen_open = true;
+                    if depth == 1 {
+                        continue
+                    }
+                }
+                ')' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return solidity_param_count(&signature)
+                    }
+                }
+                _ => {}
+            }
+            if seen_open && depth >= 1 {
+                signature.push(c);
+            }
+        }
+    }
+
+    solidity_param_count(&signature)
+}
+
+/// Like [`param_count`], but drops a leading `self`/`&self`/`&mut self` receiver before counting,
+/// since generated binding methods take one but the Solidity signature they're doc'd against
+/// never does.
+fn solidity_param_count(params: &str) -> usize {
+    let trimmed = params.trim();
+    if trimmed.is_empty() {
+        return 0
+    }
+
+    let without_receiver = match trimmed.split_once(',') {
+        Some((first, rest)) if matches!(first.trim(), "self" | "&self" | "&mut self") => rest,
+        _ if matches!(trimmed, "self" | "&self" | "&mut self") => return 0,
+        _ => trimmed,
+    };
+
+    param_count(without_receiver)
+}
+
+fn declaration_signature(line: &str) -> Option<String> {
+    for keyword in ["function ", "event "] {
+        if let Some(rest) = line.strip_prefix(keyword) {
+            let name: String = rest.chars().take_while(|c| c.is_alphanumeric() || *c == '_').collect();
+            if name.is_empty() {
+                continue
+            }
+            let after_name = &rest[name.len()..];
+            let params = match (after_name.find('('), after_name.find(')')) {
+                (Some(start), Some(end)) if start < end => &after_name[start + 1..end],
+                _ => "",
+            };
+            return Some(format!("{}({})", name, params))
+        }
+    }
+    None
+}
+
+fn format_doc(lines: &[String]) -> String {
+    lines.iter().filter(|l| !l.is_empty()).map(|l| format!("/// {}", l)).collect::<Vec<_>>().join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_compiler_output_by_full_signature_not_base_name() {
+        let userdoc = serde_json::json!({
+            "methods": {
+                "transfer(address,uint256)": { "notice": "Transfers to a single recipient." },
+                "transfer(address,address,uint256)": { "notice": "Transfers on behalf of an owner." }
+            }
+        })
+        .to_string();
+
+        let docs = from_compiler_output(None, Some(&userdoc));
+
+        assert_eq!(docs.len(), 2);
+        assert_eq!(docs["transfer(address,uint256)"], "/// Transfers to a single recipient.");
+        assert_eq!(docs["transfer(address,address,uint256)"], "/// Transfers on behalf of an owner.");
+    }
+
+    #[test]
+    fn injects_each_overload_doc_onto_its_matching_arity() {
+        let mut docs = NatSpec::new();
+        docs.insert("transfer(address,uint256)".to_owned(), "/// Transfers to a single recipient.".to_owned());
+        docs.insert("transfer(address,address,uint256)".to_owned(), "/// Transfers on behalf of an owner.".to_owned());
+
+        let source = "pub fn transfer(&self, to: Address, amount: U256) -> ContractCall {}\n\npub fn transfer(&self, from: Address, to: Address, amount: U256) -> ContractCall {}\n";
+
+        let result = inject(source, &docs);
+        let lines: Vec<&str> = result.lines().collect();
+
+        let two_arg_doc = lines.iter().position(|l| l.contains("single recipient")).unwrap();
+        let two_arg_fn = lines.iter().position(|l| l.contains("to: Address, amount: U256")).unwrap();
+        assert_eq!(two_arg_doc + 1, two_arg_fn);
+
+        let three_arg_doc = lines.iter().position(|l| l.contains("on behalf of an owner")).unwrap();
+        let three_arg_fn = lines.iter().position(|l| l.contains("from: Address, to: Address")).unwrap();
+        assert_eq!(three_arg_doc + 1, three_arg_fn);
+    }
+
+    #[test]
+    fn injects_doc_onto_arity_suffixed_overload_alias() {
+        let mut docs = NatSpec::new();
+        docs.insert("transfer(address,uint256)".to_owned(), "/// Transfers to a single recipient.".to_owned());
+        docs.insert("transfer(address,address,uint256)".to_owned(), "/// Transfers on behalf of an owner.".to_owned());
+
+        // The two-argument overload keeps the bare name; the three-argument one is aliased with a
+        // `_with_N_args` suffix, as real abigen codegen does to avoid a name clash.
+        let source = "pub fn transfer(&self, to: Address, amount: U256) -> ContractCall {}\n\npub fn transfer_with_3_args(&self, from: Address, to: Address, amount: U256) -> ContractCall {}\n";
+
+        let result = inject(source, &docs);
+        let lines: Vec<&str> = result.lines().collect();
+
+        let two_arg_doc = lines.iter().position(|l| l.contains("single recipient")).unwrap();
+        let two_arg_fn = lines.iter().position(|l| l.contains("pub fn transfer(")).unwrap();
+        assert_eq!(two_arg_doc + 1, two_arg_fn);
+
+        let three_arg_doc = lines.iter().position(|l| l.contains("on behalf of an owner")).unwrap();
+        let three_arg_fn = lines.iter().position(|l| l.contains("pub fn transfer_with_3_args(")).unwrap();
+        assert_eq!(three_arg_doc + 1, three_arg_fn);
+    }
+
+    #[test]
+    fn extracts_signature_with_params_from_solidity_source() {
+        let source = "/// Transfers tokens.\nfunction transfer(address to, uint256 amount) external;\n";
+
+        let docs = from_solidity_source(source);
+
+        assert_eq!(docs["transfer(address to, uint256 amount)"], "/// Transfers tokens.");
+    }
+
+    #[test]
+    fn param_count_ignores_commas_inside_nested_types() {
+        assert_eq!(param_count(""), 0);
+        assert_eq!(param_count("address"), 1);
+        assert_eq!(param_count("address,uint256"), 2);
+        assert_eq!(param_count("(uint256,address)[],uint256"), 2);
+    }
+}
@@ -0,0 +1,180 @@
+//! Thin wrapper around a locally installed `solc` binary used to compile Solidity sources
+//! directly, without requiring a pre-built ABI JSON on disk.
+
+use anyhow::{anyhow, Context as _, Result};
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+/// The ABI and deploy bytecode of a single contract emitted by `solc`. The bytecode is kept as
+/// `solc` emits it; library placeholders in it are not linked.
+#[derive(Debug, Clone)]
+pub struct CompiledContract {
+    /// The contract's name, as declared in the Solidity source.
+    pub name: String,
+    /// The contract's ABI, as a raw JSON string.
+    pub abi: String,
+    /// The contract's deploy (creation) bytecode, hex encoded, if `solc` produced one. Interface
+    /// contracts and libraries without a constructor body may have none.
+    pub bytecode: Option<String>,
+    /// The contract's developer-facing NatSpec documentation (`@dev`/`@param`/...), as raw JSON.
+    pub devdoc: Option<String>,
+    /// The contract's user-facing NatSpec documentation (`@notice`), as raw JSON.
+    pub userdoc: Option<String>,
+}
+
+/// Invokes `solc` on every `.sol` file found (recursively) under `dir` and returns one
+/// [`CompiledContract`] per contract the compiler emits.
+pub fn compile_dir(dir: impl AsRef<Path>) -> Result<Vec<CompiledContract>> {
+    let sources = solidity_files(dir.as_ref())?;
+    if sources.is_empty() {
+        return Err(anyhow!("no .sol files found in {}", dir.as_ref().display()))
+    }
+    compile(&sources)
+}
+
+/// Invokes `solc` on a single `.sol` file and returns one [`CompiledContract`] per contract the
+/// compiler emits from it.
+pub fn compile_file(path: impl AsRef<Path>) -> Result<Vec<CompiledContract>> {
+    compile(&[path.as_ref().to_owned()])
+}
+
+fn solidity_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in walk(dir)? {
+        if entry.extension().and_then(|ext| ext.to_str()) == Some("sol") {
+            files.push(entry);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn walk(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut out = Vec::new();
+    for entry in std::fs::read_dir(dir)
+        .with_context(|| format!("failed to read directory {}", dir.display()))?
+    {
+        let entry = entry?;
+        let path = entry.path();
+        if path.is_dir() {
+            out.extend(walk(&path)?);
+        } else {
+            out.push(path);
+        }
+    }
+    Ok(out)
+}
+
+fn compile(sources: &[PathBuf]) -> Result<Vec<CompiledContract>> {
+    let output = Command::new("solc")
+        .arg("--combined-json")
+        .arg("abi,bin,devdoc,userdoc")
+        .args(sources)
+        .output()
+        .context("failed to invoke `solc` - is it installed and on your PATH?")?;
+
+    if !output.status.success() {
+        return Err(anyhow!("solc failed:\n{}", String::from_utf8_lossy(&output.stderr)))
+    }
+
+    let combined: CombinedJson = serde_json::from_slice(&output.stdout)
+        .context("failed to parse solc --combined-json output")?;
+
+    Ok(contracts_from_combined(combined))
+}
+
+/// Converts the (hash-map keyed, so arbitrarily ordered) `solc --combined-json` output into a
+/// deterministically ordered `Vec`, sorted by contract name and then by the originating file so
+/// that repeated builds - e.g. via `MultiAbigen::from_solidity_files(..).single_file()` - produce
+/// byte-identical output instead of depending on `HashMap`'s randomized iteration order.
+fn contracts_from_combined(combined: CombinedJson) -> Vec<CompiledContract> {
+    let mut contracts: Vec<_> = combined
+        .contracts
+        .into_iter()
+        .map(|(key, contract)| {
+            // `solc --combined-json` keys contracts as `path/to/File.sol:ContractName`.
+            let name = key.rsplit(':').next().unwrap_or(&key).to_owned();
+            let bytecode = match contract.bin {
+                Some(bin) if !bin.is_empty() => Some(bin),
+                _ => None,
+            };
+            (
+                key,
+                CompiledContract {
+                    name,
+                    abi: contract.abi.to_string(),
+                    bytecode,
+                    devdoc: contract.devdoc.map(|v| v.to_string()),
+                    userdoc: contract.userdoc.map(|v| v.to_string()),
+                },
+            )
+        })
+        .collect();
+
+    contracts.sort_by(|(key_a, a), (key_b, b)| a.name.cmp(&b.name).then_with(|| key_a.cmp(key_b)));
+
+    contracts.into_iter().map(|(_, contract)| contract).collect()
+}
+
+#[derive(Deserialize)]
+struct CombinedJson {
+    contracts: HashMap<String, CombinedContract>,
+}
+
+#[derive(Deserialize)]
+struct CombinedContract {
+    abi: serde_json::Value,
+    bin: Option<String>,
+    devdoc: Option<serde_json::Value>,
+    userdoc: Option<serde_json::Value>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn combined_contract() -> CombinedContract {
+        CombinedContract { abi: serde_json::json!([]), bin: None, devdoc: None, userdoc: None }
+    }
+
+    #[test]
+    fn sorts_contracts_by_name_regardless_of_hash_map_order() {
+        let mut contracts = HashMap::new();
+        contracts.insert("b.sol:Bravo".to_owned(), combined_contract());
+        contracts.insert("a.sol:Alpha".to_owned(), combined_contract());
+        contracts.insert("c.sol:Charlie".to_owned(), combined_contract());
+
+        let result = contracts_from_combined(CombinedJson { contracts });
+
+        let names: Vec<_> = result.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["Alpha", "Bravo", "Charlie"]);
+    }
+
+    #[test]
+    fn strips_the_leading_path_from_the_combined_json_key() {
+        let mut contracts = HashMap::new();
+        contracts.insert("contracts/Token.sol:Token".to_owned(), combined_contract());
+
+        let result = contracts_from_combined(CombinedJson { contracts });
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].name, "Token");
+    }
+
+    #[test]
+    fn empty_bytecode_is_treated_as_absent() {
+        let mut contracts = HashMap::new();
+        contracts.insert(
+            "Lib.sol:Lib".to_owned(),
+            CombinedContract { abi: serde_json::json!([]), bin: Some(String::new()), devdoc: None, userdoc: None },
+        );
+
+        let result = contracts_from_combined(CombinedJson { contracts });
+
+        assert_eq!(result[0].bytecode, None);
+    }
+}
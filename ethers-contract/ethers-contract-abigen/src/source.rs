@@ -0,0 +1,276 @@
+//! Facility for retrieving ABI JSON from various sources.
+
+use anyhow::{anyhow, Context as _, Result};
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+/// The path to a local cache of ABIs fetched over the network, rooted at the OS cache
+/// directory (or a temporary directory as a fallback). Contents are keyed by chain id and
+/// address so that repeated `build.rs` runs (e.g. via `MultiAbigen::from_json_files`-style
+/// usage) stay offline-reproducible instead of re-fetching the ABI on every build.
+fn etherscan_cache_dir() -> PathBuf {
+    dirs_next_cache_dir().join("ethers-rs").join("abigen").join("etherscan")
+}
+
+// Minimal stand-in for the `dirs` crate's `cache_dir`, kept local so this module has no new
+// dependency beyond what `Abigen` already needs to hit the network.
+fn dirs_next_cache_dir() -> PathBuf {
+    env::var_os("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".cache")))
+        .unwrap_or_else(env::temp_dir)
+}
+
+/// The source of a contract's ABI JSON.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    /// Human-readable ABI or raw ABI JSON, provided directly as a string.
+    String(String),
+
+    /// ABI JSON loaded from a local file.
+    Local(PathBuf),
+
+    /// ABI JSON fetched from a block explorer for the given contract address.
+    ///
+    /// The explorer's API key is read from the `ETHERSCAN_API_KEY` environment variable, and
+    /// the chain to query is read from `ETHERSCAN_CHAIN_ID` (defaulting to Ethereum mainnet,
+    /// chain id `1`).
+    Etherscan(ethers_core::types::Address),
+
+    /// A single Solidity file to compile with a locally installed `solc`.
+    ///
+    /// The file must contain exactly one contract; use [`crate::MultiAbigen::from_solidity_files`]
+    /// to generate bindings for every contract in a directory instead.
+    Solidity(PathBuf),
+}
+
+impl Source {
+    /// Parses a source from a string.
+    ///
+    /// The following forms are recognized:
+    /// - `etherscan:0x...` explicitly requests an Etherscan-style explorer lookup; the address
+    ///   is not required to be checksummed, since the intent is unambiguous
+    /// - a bare, 20-byte hex address is treated the same way, but only if it's unambiguous:
+    ///   all-lowercase, all-uppercase, or a valid EIP-55 checksum. A mixed-case string that fails
+    ///   the checksum is rejected, since it's more likely a typo than a contract address.
+    /// - a path ending in `.sol` is compiled with `solc` (whether or not the file exists yet)
+    /// - a path to an existing file is read from disk
+    /// - anything else is treated as an inline ABI (human-readable or raw JSON)
+    pub fn parse(source: &str) -> Result<Self> {
+        if let Some(address) = source.strip_prefix("etherscan:") {
+            return Ok(Source::Etherscan(crate::parse_address(address)?))
+        }
+
+        if is_unambiguous_address(source) {
+            if let Ok(address) = crate::parse_address(source) {
+                return Ok(Source::Etherscan(address))
+            }
+        }
+
+        let path = Path::new(source);
+        if path.extension().and_then(|ext| ext.to_str()) == Some("sol") {
+            return Ok(Source::Solidity(path.to_owned()))
+        }
+        if path.is_file() {
+            return Ok(Source::Local(path.to_owned()))
+        }
+
+        Ok(Source::String(source.to_owned()))
+    }
+
+    /// Resolves this source into the raw ABI JSON (or human-readable ABI) string.
+    pub fn get(&self) -> Result<String> {
+        match self {
+            Source::String(abi) => Ok(abi.clone()),
+            Source::Local(path) => fs::read_to_string(path)
+                .with_context(|| format!("failed to read ABI from {}", path.display())),
+            Source::Etherscan(address) => get_etherscan_source(*address, etherscan_chain_id()),
+            Source::Solidity(path) => {
+                let mut contracts = crate::solc::compile_file(path)?;
+                match contracts.len() {
+                    1 => Ok(contracts.remove(0).abi),
+                    0 => Err(anyhow!("{} did not produce any contracts", path.display())),
+                    _ => Err(anyhow!(
+                        "{} contains more than one contract; use `MultiAbigen::from_solidity_files` instead",
+                        path.display()
+                    )),
+                }
+            }
+        }
+    }
+}
+
+/// Whether a bare address string is safe to treat as an Etherscan lookup rather than, say, an
+/// inline ABI string that merely happens to be 40 hex characters.
+///
+/// A hex address with no case mixing (all-lowercase or all-uppercase) carries no EIP-55 checksum
+/// information, so it's accepted as-is. A mixed-case string is only accepted if it matches the
+/// EIP-55 checksum for the address it encodes - otherwise it's far more likely to be a typo'd
+/// address (or something else entirely) than a deliberate source.
+fn is_unambiguous_address(source: &str) -> bool {
+    let hex = source.strip_prefix("0x").unwrap_or(source);
+    if hex.len() != 40 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return false
+    }
+
+    if hex.chars().all(|c| !c.is_ascii_alphabetic() || c.is_ascii_lowercase())
+        || hex.chars().all(|c| !c.is_ascii_alphabetic() || c.is_ascii_uppercase())
+    {
+        return true
+    }
+
+    let Ok(address) = crate::parse_address(source) else { return false };
+    ethers_core::utils::to_checksum(&address, None) == format!("0x{}", hex)
+}
+
+impl FromStr for Source {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Source::parse(s)
+    }
+}
+
+/// Chain id queried against Etherscan-compatible explorers. Defaults to Ethereum mainnet.
+fn etherscan_chain_id() -> u64 {
+    env::var("ETHERSCAN_CHAIN_ID").ok().and_then(|s| s.parse().ok()).unwrap_or(1)
+}
+
+/// Fetches (and caches) the verified ABI for `address` on `chain_id` from an Etherscan-compatible
+/// explorer.
+///
+/// `chain_id` is taken as a parameter, rather than read from `ETHERSCAN_CHAIN_ID` internally, so
+/// tests can exercise caching behavior for a specific chain id without mutating shared process
+/// environment state (which would race with any other test reading the same env var).
+fn get_etherscan_source(address: ethers_core::types::Address, chain_id: u64) -> Result<String> {
+    let cache_path = etherscan_cache_dir().join(chain_id.to_string()).join(format!("{:?}.json", address));
+
+    if let Ok(cached) = fs::read_to_string(&cache_path) {
+        return Ok(cached)
+    }
+
+    let result = fetch_etherscan_abi(address, chain_id)?;
+
+    if let Some(parent) = cache_path.parent() {
+        fs::create_dir_all(parent).ok();
+    }
+    fs::write(&cache_path, &result).ok();
+
+    Ok(result)
+}
+
+#[cfg(feature = "online")]
+fn fetch_etherscan_abi(address: ethers_core::types::Address, chain_id: u64) -> Result<String> {
+    let api_key = env::var("ETHERSCAN_API_KEY")
+        .context("ETHERSCAN_API_KEY must be set to resolve an `etherscan:` ABI source")?;
+
+    let url = format!(
+        "https://api.etherscan.io/api?module=contract&action=getabi&address={:?}&apikey={}&chainid={}",
+        address, api_key, chain_id
+    );
+
+    let response: EtherscanResponse = reqwest::blocking::get(&url)
+        .context("failed to reach block explorer")?
+        .json()
+        .context("failed to parse block explorer response")?;
+
+    if response.status != "1" {
+        return Err(anyhow!("block explorer returned an error: {}", response.result))
+    }
+
+    Ok(response.result)
+}
+
+#[cfg(not(feature = "online"))]
+fn fetch_etherscan_abi(_address: ethers_core::types::Address, _chain_id: u64) -> Result<String> {
+    Err(anyhow!(
+        "resolving an `etherscan:` ABI source requires the `online` feature of ethers-contract-abigen"
+    ))
+}
+
+#[cfg(feature = "online")]
+#[derive(serde::Deserialize)]
+struct EtherscanResponse {
+    status: String,
+    result: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // WETH9's real, EIP-55 checksummed mainnet address.
+    const WETH9_CHECKSUMMED: &str = "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2";
+
+    #[test]
+    fn parses_checksummed_address_as_etherscan_source() {
+        let source = Source::parse(WETH9_CHECKSUMMED).unwrap();
+        match source {
+            Source::Etherscan(address) => {
+                assert_eq!(ethers_core::utils::to_checksum(&address, None), WETH9_CHECKSUMMED)
+            }
+            other => panic!("expected Source::Etherscan, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parses_all_lowercase_address_as_etherscan_source() {
+        let source = Source::parse(&WETH9_CHECKSUMMED.to_lowercase()).unwrap();
+        assert!(matches!(source, Source::Etherscan(_)));
+    }
+
+    #[test]
+    fn rejects_mixed_case_address_with_bad_checksum() {
+        // Flip the case of one letter so the checksum no longer matches; this must not be
+        // silently accepted as an Etherscan lookup.
+        let bad = WETH9_CHECKSUMMED.replacen('C', "c", 1);
+        let source = Source::parse(&bad).unwrap();
+        assert!(!matches!(source, Source::Etherscan(_)));
+    }
+
+    #[test]
+    fn parses_etherscan_prefixed_address_without_checksum_validation() {
+        let source = Source::parse(&format!("etherscan:{}", WETH9_CHECKSUMMED.to_lowercase())).unwrap();
+        assert!(matches!(source, Source::Etherscan(_)));
+    }
+
+    #[test]
+    fn parses_solidity_file_path() {
+        let source = Source::parse("Contract.sol").unwrap();
+        assert_eq!(source, Source::Solidity(PathBuf::from("Contract.sol")));
+    }
+
+    #[test]
+    fn parses_inline_abi_string() {
+        let source = Source::parse("[]").unwrap();
+        assert_eq!(source, Source::String("[]".to_owned()));
+    }
+
+    #[test]
+    fn get_etherscan_source_returns_cached_value_without_network_access() {
+        // A chain id unlikely to collide with another test's cache entry, since the cache
+        // directory is shared process (and filesystem) state.
+        let address = ethers_core::types::Address::from_slice(&[0x11; 20]);
+        let chain_id = 999_999_001u64;
+
+        let cache_dir = etherscan_cache_dir().join(chain_id.to_string());
+        fs::create_dir_all(&cache_dir).unwrap();
+        fs::write(cache_dir.join(format!("{:?}.json", address)), "[\"cached\"]").unwrap();
+
+        let result = get_etherscan_source(address, chain_id).unwrap();
+
+        assert_eq!(result, "[\"cached\"]");
+    }
+
+    #[test]
+    fn etherscan_chain_id_defaults_to_mainnet_when_unset() {
+        // This test neither sets nor clears `ETHERSCAN_CHAIN_ID`; the assertion only runs when
+        // the variable is unset in the test process's environment.
+        if std::env::var_os("ETHERSCAN_CHAIN_ID").is_none() {
+            assert_eq!(etherscan_chain_id(), 1);
+        }
+    }
+}